@@ -0,0 +1,133 @@
+use roaring::RoaringBitmap;
+
+use crate::scan::scan;
+use crate::union_find::UnionFind;
+use crate::{Connectivity, LabelType};
+
+/// It executes connected-component labeling and returns each component as a
+/// [`RoaringBitmap`] of flattened pixel indices (`y * width + x`).
+///
+/// Unlike returning a `Vec` of pixel coordinates per component, a
+/// `RoaringBitmap` stores dense runs as run-length/array/bitmap containers
+/// automatically, so a component spanning millions of pixels costs only a
+/// few containers. This also enables fast set operations (union,
+/// intersection, ...) between components, including across frames.
+///
+/// # Arguments
+///
+/// * `binary_image` - Binary image represented by a two-dimensional boolean vector
+/// * `conn` - Neighborhood used to decide whether two pixels are connected
+///
+/// # Return
+///
+/// A `RoaringBitmap` per component, indexed by the reassigned label.
+///
+/// # Panics
+///
+/// The function panics if `binary_image` is not a rectangle.
+pub fn component_bitmaps(binary_image: &Vec<Vec<bool>>, conn: Connectivity) -> Vec<RoaringBitmap> {
+    let width = binary_image[0].len() as u32;
+
+    // Run the provisional-labeling pass shared with `labeling_with_stats`.
+    let (labeled_image, mut uf) = scan(binary_image, conn);
+
+    reassign_to_bitmaps(&labeled_image, &mut uf, width)
+}
+
+/// It reassigns provisional labels, inserting each foreground pixel's
+/// flattened index into its reassigned label's `RoaringBitmap`.
+fn reassign_to_bitmaps(
+    labeled_image: &Vec<Vec<LabelType>>,
+    uf: &mut UnionFind,
+    width: u32,
+) -> Vec<RoaringBitmap> {
+    // Height of image
+    let h = labeled_image.len();
+
+    // Width of image
+    let w = labeled_image[0].len();
+
+    // Vec for reassigned labels
+    // The i-th index stores the reassigned label for provisional label i.
+    let mut reassignment_labels: Vec<Option<LabelType>> = vec![None; uf.get_elem_num()];
+
+    // Counting the number of labels
+    let mut label_cnt = 0;
+
+    let mut bitmaps: Vec<RoaringBitmap> = vec![];
+
+    for i in 0..h {
+        for j in 0..w {
+            // If the current pixel is background, skip the process.
+            if labeled_image[i][j] == -1 {
+                continue;
+            }
+
+            // Representative value of the connected component containing the label of the current pixel
+            let leader_label = uf.leader(labeled_image[i][j] as usize);
+            // Reassigned label
+            let reassigned_label = &mut reassignment_labels[leader_label];
+
+            // If no label has been set for reassignment, create a new label.
+            if reassigned_label.is_none() {
+                *reassigned_label = Some(label_cnt);
+                label_cnt += 1;
+                bitmaps.push(RoaringBitmap::new());
+            }
+
+            // Insert the flattened pixel index into the component's bitmap.
+            let flat_index = j as u32 + i as u32 * width;
+            bitmaps[reassigned_label.unwrap() as usize].insert(flat_index);
+        }
+    }
+
+    bitmaps
+}
+
+/// Returns whether pixel `(x, y)` belongs to the component represented by `bitmap`.
+pub fn component_contains(bitmap: &RoaringBitmap, width: u32, x: u32, y: u32) -> bool {
+    bitmap.contains(x + y * width)
+}
+
+/// Iterates over the `(x, y)` coordinates of every pixel in `bitmap`.
+pub fn component_pixels(bitmap: &RoaringBitmap, width: u32) -> impl Iterator<Item = (u32, u32)> + '_ {
+    bitmap.iter().map(move |flat_index| (flat_index % width, flat_index / width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_one_bitmap_per_component() {
+        // # # .
+        // . . .
+        // . . #
+        let binary_image = vec![
+            vec![true, true, false],
+            vec![false, false, false],
+            vec![false, false, true],
+        ];
+
+        let bitmaps = component_bitmaps(&binary_image, Connectivity::Eight);
+
+        assert_eq!(bitmaps.len(), 2);
+
+        let width = 3;
+        let top_run = bitmaps
+            .iter()
+            .find(|b| component_contains(b, width, 0, 0))
+            .expect("top run component");
+        assert!(component_contains(top_run, width, 1, 0));
+        assert!(!component_contains(top_run, width, 2, 2));
+        let mut pixels: Vec<_> = component_pixels(top_run, width).collect();
+        pixels.sort();
+        assert_eq!(pixels, vec![(0, 0), (1, 0)]);
+
+        let corner = bitmaps
+            .iter()
+            .find(|b| component_contains(b, width, 2, 2))
+            .expect("bottom-right pixel component");
+        assert_eq!(corner.len(), 1);
+    }
+}
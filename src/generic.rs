@@ -0,0 +1,168 @@
+use image::{GenericImageView, ImageBuffer, Luma};
+
+use crate::union_find::UnionFind;
+
+/// Neighborhood used when deciding whether two foreground pixels belong to
+/// the same connected component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Only the upper and left pixels are considered neighbors.
+    Four,
+    /// The upper, upper-left, upper-right and left pixels are considered neighbors.
+    Eight,
+}
+
+/// It labels the connected components of `img`.
+///
+/// Every pixel equal to `background` is treated as background. Every other
+/// pixel is potential foreground, and two neighboring foreground pixels are
+/// merged into the same component only if they hold the same pixel value.
+/// This allows multi-valued masks (not just 0/1 images) to be labeled.
+///
+/// # Arguments
+///
+/// * `img` - Source image
+/// * `conn` - Neighborhood used to decide whether two pixels are connected
+/// * `background` - Pixel value treated as background
+///
+/// # Return
+///
+/// A label image the same size as `img`, where `0` marks background and each
+/// connected component is assigned a distinct label starting at `1`.
+///
+/// # Panics
+///
+/// The function panics if the width or height of `img` is zero.
+pub fn connected_components<I>(
+    img: &I,
+    conn: Connectivity,
+    background: I::Pixel,
+) -> ImageBuffer<Luma<u32>, Vec<u32>>
+where
+    I: GenericImageView,
+    I::Pixel: PartialEq,
+{
+    let w = img.width();
+    let h = img.height();
+    assert!(w >= 1, "Width must be greater than or equal to 1.");
+    assert!(h >= 1, "Height must be greater than or equal to 1.");
+
+    let idx = |x: u32, y: u32| (x + y * w) as usize;
+
+    // Provisional label for each pixel (0 = background, otherwise a 1-based label).
+    let mut provisional = vec![0u32; (w * h) as usize];
+
+    let mut uf = UnionFind::new(0);
+
+    // Offsets of the already-visited neighbors to examine, depending on connectivity.
+    let neighbor_offsets: &[(i64, i64)] = match conn {
+        Connectivity::Four => &[(0, -1), (-1, 0)],
+        Connectivity::Eight => &[(-1, -1), (0, -1), (1, -1), (-1, 0)],
+    };
+
+    for y in 0..h {
+        for x in 0..w {
+            let pixel = img.get_pixel(x, y);
+            if pixel == background {
+                continue;
+            }
+
+            // Labels of the already-visited neighbors that hold the same pixel value.
+            // Each candidate also needs a pixel-value comparison
+            // (`img.get_pixel(nx, ny) == pixel`). The labels are collected
+            // into a `Vec` rather than left as a lazy iterator because the
+            // filter closure borrows `provisional`, and that borrow must end
+            // before `provisional[idx(x, y)]` is written below.
+            let neighbor_labels: Vec<u32> = neighbor_offsets
+                .iter()
+                .filter_map(|&(dx, dy)| {
+                    let nx = x as i64 + dx;
+                    let ny = y as i64 + dy;
+                    if nx < 0 || ny < 0 || nx >= w as i64 || ny >= h as i64 {
+                        return None;
+                    }
+                    let (nx, ny) = (nx as u32, ny as u32);
+                    let neighbor_label = provisional[idx(nx, ny)];
+                    if neighbor_label != 0 && img.get_pixel(nx, ny) == pixel {
+                        Some(neighbor_label)
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            if let Some((&first_label, rest)) = neighbor_labels.split_first() {
+                // Set the current pixel's label to the first matching neighbor's label.
+                provisional[idx(x, y)] = first_label;
+                // Merge labels of all other matching neighbors on union-find.
+                rest.iter().for_each(|&other_label| {
+                    uf.merge((first_label - 1) as usize, (other_label - 1) as usize);
+                });
+            } else {
+                // No matching neighbor was found, assign a new label.
+                provisional[idx(x, y)] = uf.get_elem_num() as u32 + 1;
+                uf.add();
+            }
+        }
+    }
+
+    // Reassign labels.
+    let mut reassignment_labels: Vec<Option<u32>> = vec![None; uf.get_elem_num()];
+    let mut label_cnt = 0u32;
+    let mut out = vec![0u32; (w * h) as usize];
+
+    for y in 0..h {
+        for x in 0..w {
+            let label = provisional[idx(x, y)];
+            if label == 0 {
+                continue;
+            }
+
+            let leader_label = uf.leader((label - 1) as usize);
+            let reassigned_label = &mut reassignment_labels[leader_label];
+
+            if reassigned_label.is_none() {
+                label_cnt += 1;
+                *reassigned_label = Some(label_cnt);
+            }
+
+            out[idx(x, y)] = reassigned_label.unwrap();
+        }
+    }
+
+    ImageBuffer::from_raw(w, h, out).expect("buffer size must match image dimensions")
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GrayImage, Luma};
+
+    use super::*;
+
+    #[test]
+    fn labels_same_valued_neighbors_and_separates_other_values() {
+        // 1 1 0
+        // 0 1 0
+        // 0 0 2
+        let values = [[1, 1, 0], [0, 1, 0], [0, 0, 2]];
+        let img = GrayImage::from_fn(3, 3, |x, y| Luma([values[y as usize][x as usize]]));
+
+        let labeled = connected_components(&img, Connectivity::Four, Luma([0]));
+
+        let label = |x, y| labeled.get_pixel(x, y).0[0];
+
+        // The three `1`-valued pixels merge into a single component.
+        assert_ne!(label(0, 0), 0);
+        assert_eq!(label(0, 0), label(1, 0));
+        assert_eq!(label(0, 0), label(1, 1));
+
+        // The `2`-valued pixel is a distinct component, even though it is
+        // adjacent to background, because it does not match the background value.
+        assert_ne!(label(2, 2), 0);
+        assert_ne!(label(2, 2), label(0, 0));
+
+        // Background pixels stay labeled `0`.
+        assert_eq!(label(2, 0), 0);
+        assert_eq!(label(0, 2), 0);
+    }
+}
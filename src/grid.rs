@@ -0,0 +1,184 @@
+use std::ops::{Index, IndexMut};
+
+/// Flat, contiguously-stored image buffer.
+///
+/// Internally this holds a single `Vec<T>` of size `width * height` instead
+/// of a `Vec<Vec<T>>`, which avoids the pointer-chasing and per-row bounds
+/// checks that a nested vector incurs in hot scan loops. Because the buffer
+/// is built up front from `width` and `height`, it is impossible to end up
+/// with a ragged image.
+#[derive(Debug, Clone)]
+pub struct Image<T> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone> Image<T> {
+    /// It creates an image of size `width x height`, with every pixel set to `fill`.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `width` or `height` is zero.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        assert!(width >= 1, "Width must be greater than or equal to 1.");
+        assert!(height >= 1, "Height must be greater than or equal to 1.");
+
+        Image {
+            width,
+            height,
+            data: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Image<T> {
+    /// Width of the image.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the image.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Offset of pixel `(x, y)` in the flat buffer.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `x >= self.width()` or `y >= self.height()`.
+    /// Without this check, an out-of-range `x` can still land inside the
+    /// flat buffer (e.g. `width=3, x=4` wrapping into the next row) and
+    /// silently read or write the wrong pixel instead of panicking like the
+    /// `Vec<Vec<T>>` this type replaces.
+    fn offset(&self, x: usize, y: usize) -> usize {
+        assert!(x < self.width, "x out of bounds.");
+        assert!(y < self.height, "y out of bounds.");
+        x + y * self.width
+    }
+}
+
+impl<T> Index<(usize, usize)> for Image<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// The function panics if `x` or `y` is out of bounds.
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.data[self.offset(x, y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Image<T> {
+    /// # Panics
+    ///
+    /// The function panics if `x` or `y` is out of bounds.
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let offset = self.offset(x, y);
+        &mut self.data[offset]
+    }
+}
+
+impl<T: Clone> From<&Vec<Vec<T>>> for Image<T> {
+    /// # Panics
+    ///
+    /// The function panics if `rows` is not a rectangle.
+    fn from(rows: &Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        assert!(height >= 1, "Height must be greater than or equal to 1.");
+
+        let width = rows[0].len();
+        assert!(width >= 1, "Width must be greater than or equal to 1");
+
+        let mut data = Vec::with_capacity(width * height);
+        for row in rows {
+            assert_eq!(row.len(), width, "Image shape must be rectangular.");
+            data.extend(row.iter().cloned());
+        }
+
+        Image {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+impl<T: Clone> From<&Image<T>> for Vec<Vec<T>> {
+    fn from(image: &Image<T>) -> Self {
+        (0..image.height())
+            .map(|y| {
+                (0..image.width())
+                    .map(|x| image[(x, y)].clone())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_by_x_y_and_supports_mutation() {
+        let mut image = Image::new(3, 2, 0);
+
+        assert_eq!(image.width(), 3);
+        assert_eq!(image.height(), 2);
+        assert_eq!(image[(0, 0)], 0);
+
+        image[(2, 1)] = 7;
+
+        assert_eq!(image[(2, 1)], 7);
+        // Writing one pixel must not disturb its neighbors.
+        assert_eq!(image[(1, 1)], 0);
+        assert_eq!(image[(2, 0)], 0);
+    }
+
+    #[test]
+    fn round_trips_through_nested_vec() {
+        let rows = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+        let image = Image::from(&rows);
+        assert_eq!(image[(0, 0)], 1);
+        assert_eq!(image[(2, 0)], 3);
+        assert_eq!(image[(0, 1)], 4);
+        assert_eq!(image[(2, 1)], 6);
+
+        let round_tripped: Vec<Vec<i32>> = Vec::from(&image);
+        assert_eq!(round_tripped, rows);
+    }
+
+    #[test]
+    #[should_panic(expected = "Image shape must be rectangular.")]
+    fn from_nested_vec_panics_on_ragged_input() {
+        let rows = vec![vec![1, 2, 3], vec![4, 5]];
+        let _ = Image::from(&rows);
+    }
+
+    #[test]
+    #[should_panic(expected = "x out of bounds.")]
+    fn index_panics_when_x_is_out_of_bounds() {
+        let image = Image::new(3, 2, 0);
+        let _ = image[(3, 0)];
+    }
+
+    #[test]
+    #[should_panic(expected = "y out of bounds.")]
+    fn index_panics_when_y_is_out_of_bounds() {
+        let image = Image::new(3, 2, 0);
+        let _ = image[(0, 2)];
+    }
+
+    #[test]
+    #[should_panic(expected = "x out of bounds.")]
+    fn index_does_not_silently_wrap_into_the_next_row() {
+        // width=3, so x=4 would land at flat offset 4, which is still
+        // `< width * height` (6) -- this must panic rather than silently
+        // resolve to pixel (1, 1).
+        let image = Image::new(3, 2, 0);
+        let _ = image[(4, 0)];
+    }
+}
@@ -1,7 +1,19 @@
+mod bitmap;
+mod generic;
+mod grid;
+mod scan;
+mod stats;
+mod streaming;
 mod union_find;
 
 use crate::union_find::UnionFind;
 
+pub use bitmap::{component_bitmaps, component_contains, component_pixels};
+pub use generic::{connected_components, Connectivity};
+pub use grid::Image;
+pub use stats::{labeling_with_stats, RegionStats};
+pub use streaming::{Labeler, Remapper};
+
 /// Data type of label
 pub type LabelType = i32;
 
@@ -19,37 +31,34 @@ pub type LabelType = i32;
 ///
 /// The function panics if `binary_image` is not a rectangle.
 pub fn four_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<Vec<LabelType>> {
-    // Height of image
-    let h = binary_image.len();
-    assert!(h >= 1, "Height must be greater than or equal to 1.");
+    // Flatten the input into a single contiguous buffer. This also checks
+    // that `binary_image` is a non-empty rectangle.
+    let binary_image = Image::from(binary_image);
 
-    // Width of image
-    let w = binary_image[0].len();
-    assert!(w >= 1, "Width must be greater than or equal to 1");
+    let w = binary_image.width();
+    let h = binary_image.height();
 
-    let mut labeled_image = vec![vec![-1; w]; h];
+    let mut labeled_image = Image::new(w, h, -1);
 
     let mut uf = UnionFind::new(0);
 
     for i in 0..h {
-        assert_eq!(binary_image[i].len(), w, "Image shape must be rectangular.");
-
         for j in 0..w {
             // If the current pixel is background, skip the process.
-            if !binary_image[i][j] {
+            if !binary_image[(j, i)] {
                 continue;
             }
 
             // Label of the upper pixel
-            let upper_label = if i > 0 && binary_image[i - 1][j] {
-                labeled_image[i - 1][j]
+            let upper_label = if i > 0 && binary_image[(j, i - 1)] {
+                labeled_image[(j, i - 1)]
             } else {
                 -1
             };
 
             // Label of the left pixel
-            let left_label = if j > 0 && binary_image[i][j - 1] {
-                labeled_image[i][j - 1]
+            let left_label = if j > 0 && binary_image[(j - 1, i)] {
+                labeled_image[(j - 1, i)]
             } else {
                 -1
             };
@@ -58,21 +67,21 @@ pub fn four_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<Ve
                 // If both the upper pixel and the left pixel are in the foreground
 
                 // Set the current label to the label of the upper pixel.
-                labeled_image[i][j] = upper_label;
+                labeled_image[(j, i)] = upper_label;
                 // Merge labels on union-find.
                 uf.merge(upper_label as usize, left_label as usize);
             } else if upper_label != -1 {
                 // If only the upper pixel is in the foreground,
                 // set the current label to the label of the upper pixel.
-                labeled_image[i][j] = upper_label;
+                labeled_image[(j, i)] = upper_label;
             } else if left_label != -1 {
                 // If only the left pixel is in the foreground,
                 // set the current label to the label of the left pixel.
-                labeled_image[i][j] = left_label;
+                labeled_image[(j, i)] = left_label;
             } else {
                 // If neither the upper pixel nor the left pixel is in the foreground,
                 // assign a new label to the current pixel.
-                labeled_image[i][j] = uf.get_elem_num() as LabelType;
+                labeled_image[(j, i)] = uf.get_elem_num() as LabelType;
                 uf.add();
             }
         }
@@ -81,7 +90,7 @@ pub fn four_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<Ve
     // Reassign labels.
     reassign_labels(&mut labeled_image, &mut uf);
 
-    labeled_image
+    Vec::from(&labeled_image)
 }
 
 /// It execute eight-neighborhood-based labeling.
@@ -98,51 +107,48 @@ pub fn four_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<Ve
 ///
 /// The function panics if `binary_image` is not a rectangle.
 pub fn eight_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<Vec<LabelType>> {
-    // Height of image
-    let h = binary_image.len();
-    assert!(h >= 1, "Height must be greater than or equal to 1.");
+    // Flatten the input into a single contiguous buffer. This also checks
+    // that `binary_image` is a non-empty rectangle.
+    let binary_image = Image::from(binary_image);
 
-    // Width of image
-    let w = binary_image[0].len();
-    assert!(w >= 1, "Width must be greater than or equal to 1");
+    let w = binary_image.width();
+    let h = binary_image.height();
 
     let mut uf = UnionFind::new(0);
 
-    let mut labeled_image = vec![vec![-1; w]; h];
+    let mut labeled_image = Image::new(w, h, -1);
 
     for i in 0..h {
-        assert_eq!(binary_image[i].len(), w, "Image shape must be rectangular.");
-
         for j in 0..w {
             // If the current pixel is background, skip the process.
-            if !binary_image[i][j] {
+            if !binary_image[(j, i)] {
                 continue;
             }
 
             // Label of the upper left pixel
-            let upper_left_label = if i > 0 && j > 0 && binary_image[i - 1][j - 1] {
-                labeled_image[i - 1][j - 1]
+            let upper_left_label = if i > 0 && j > 0 && binary_image[(j - 1, i - 1)] {
+                labeled_image[(j - 1, i - 1)]
             } else {
                 -1
             };
 
             // Label of the upper pixel
-            let upper_label = if i > 0 && binary_image[i - 1][j] {
-                labeled_image[i - 1][j]
+            let upper_label = if i > 0 && binary_image[(j, i - 1)] {
+                labeled_image[(j, i - 1)]
             } else {
                 -1
             };
 
             // Label of the upper right pixel
-            let upper_right_label = if i > 0 && j < w - 1 && binary_image[i - 1][j + 1] {
-                labeled_image[i - 1][j + 1]
+            let upper_right_label = if i > 0 && j < w - 1 && binary_image[(j + 1, i - 1)] {
+                labeled_image[(j + 1, i - 1)]
             } else {
                 -1
             };
 
             // Label of the left pixel
-            let left_label = if j > 0 && binary_image[i][j - 1] {
-                labeled_image[i][j - 1]
+            let left_label = if j > 0 && binary_image[(j - 1, i)] {
+                labeled_image[(j - 1, i)]
             } else {
                 -1
             };
@@ -157,7 +163,7 @@ pub fn eight_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<V
                 // If foreground pixels are found
 
                 // Set the label of the current pixel to the label of that pixel.
-                labeled_image[i][j] = foreground_label;
+                labeled_image[(j, i)] = foreground_label;
                 // Merge with all other labels of foreground pixels on union-find.
                 foreground_labels.for_each(|x| {
                     uf.merge(foreground_label as usize, *x as usize);
@@ -165,7 +171,7 @@ pub fn eight_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<V
             } else {
                 // If no foreground pixels are found.
                 // assign a new label to the current pixel.
-                labeled_image[i][j] = uf.get_elem_num() as LabelType;
+                labeled_image[(j, i)] = uf.get_elem_num() as LabelType;
                 uf.add();
             }
         }
@@ -174,7 +180,7 @@ pub fn eight_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<V
     // Reassign labels.
     reassign_labels(&mut labeled_image, &mut uf);
 
-    labeled_image
+    Vec::from(&labeled_image)
 }
 
 /// It reassign labels.
@@ -187,18 +193,10 @@ pub fn eight_neighborhood_based_labeling(binary_image: &Vec<Vec<bool>>) -> Vec<V
 /// # Panics
 ///
 /// This function panics if the height or width of the image is zero.
-fn reassign_labels(labeled_image: &mut Vec<Vec<LabelType>>, uf: &mut UnionFind) {
-    // Height of image
-    let h = labeled_image.len();
-
-    // Height must be greater than or equal to 1
-    assert!(h >= 1);
-
-    // Width of image
-    let w = labeled_image[0].len();
-
-    // Width must be greater than or equal to 1
-    assert!(w >= 1);
+pub(crate) fn reassign_labels(labeled_image: &mut Image<LabelType>, uf: &mut UnionFind) {
+    // Width and height of image
+    let w = labeled_image.width();
+    let h = labeled_image.height();
 
     // Vec for reassigned labels
     // The i-th index stores the reassigned label for provisional label i.
@@ -210,12 +208,12 @@ fn reassign_labels(labeled_image: &mut Vec<Vec<LabelType>>, uf: &mut UnionFind)
     for i in 0..h {
         for j in 0..w {
             // If the current pixel is background, skip the process.
-            if labeled_image[i][j] == -1 {
+            if labeled_image[(j, i)] == -1 {
                 continue;
             }
 
             // Representative value of the connected component containing the label of the current pixel
-            let leader_label = uf.leader(labeled_image[i][j] as usize);
+            let leader_label = uf.leader(labeled_image[(j, i)] as usize);
             // Reassigned label
             let reassigned_label = &mut reassignment_labels[leader_label];
 
@@ -226,7 +224,7 @@ fn reassign_labels(labeled_image: &mut Vec<Vec<LabelType>>, uf: &mut UnionFind)
             }
 
             // Reassign labels.
-            labeled_image[i][j] = reassigned_label.unwrap();
+            labeled_image[(j, i)] = reassigned_label.unwrap();
         }
     }
 }
@@ -1,12 +1,220 @@
+use std::env;
 use std::io;
 
+use image::{Rgb, RgbImage};
+
 /// Characters representing the foreground.
 const BACKGROUND_CHARACTER: char = '0';
 
 /// Characters representing the background.
 const FOREGROUND_CHARACTER: char = '1';
 
+/// Default grayscale threshold used to binarize an input raster file.
+const DEFAULT_THRESHOLD: u8 = 128;
+
+/// Default path the colorized label map is written to.
+const DEFAULT_OUTPUT_PATH: &str = "labeled.png";
+
+/// Which side of the threshold is treated as background.
+#[derive(Debug, Clone, Copy)]
+enum BackgroundSide {
+    /// Pixels darker than the threshold are background.
+    Black,
+    /// Pixels brighter than the threshold are background.
+    White,
+}
+
+impl BackgroundSide {
+    fn parse(s: &str) -> Self {
+        match s {
+            "black" => BackgroundSide::Black,
+            "white" => BackgroundSide::White,
+            _ => panic!("--background must be \"black\" or \"white\"."),
+        }
+    }
+
+    /// Whether a pixel with the given luma value is foreground.
+    fn is_foreground(self, luma: u8, threshold: u8) -> bool {
+        match self {
+            BackgroundSide::Black => luma >= threshold,
+            BackgroundSide::White => luma < threshold,
+        }
+    }
+}
+
+/// Connectivity selected from the command line.
+#[derive(Debug, Clone, Copy)]
+enum ConnectivityArg {
+    Four,
+    Eight,
+}
+
+impl ConnectivityArg {
+    fn parse(s: &str) -> Self {
+        match s {
+            "four" => ConnectivityArg::Four,
+            "eight" => ConnectivityArg::Eight,
+            _ => panic!("--connectivity must be \"four\" or \"eight\"."),
+        }
+    }
+
+    fn label(self, binary_image: &Vec<Vec<bool>>) -> Vec<Vec<labeling::LabelType>> {
+        match self {
+            ConnectivityArg::Four => labeling::four_neighborhood_based_labeling(binary_image),
+            ConnectivityArg::Eight => labeling::eight_neighborhood_based_labeling(binary_image),
+        }
+    }
+}
+
+/// Options for the raster-file CLI mode.
+struct ImageModeArgs {
+    image_path: String,
+    output_path: String,
+    threshold: u8,
+    background: BackgroundSide,
+    connectivity: ConnectivityArg,
+}
+
+impl ImageModeArgs {
+    /// It parses `--image`, `--output`, `--threshold`, `--background` and
+    /// `--connectivity` out of the command-line arguments.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `--image` is missing, an unknown flag is
+    /// given, or a flag's value cannot be parsed.
+    fn parse(args: &[String]) -> Self {
+        let mut image_path = None;
+        let mut output_path = DEFAULT_OUTPUT_PATH.to_string();
+        let mut threshold = DEFAULT_THRESHOLD;
+        let mut background = BackgroundSide::Black;
+        let mut connectivity = ConnectivityArg::Eight;
+
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--image" => {
+                    i += 1;
+                    image_path = Some(args[i].clone());
+                }
+                "--output" => {
+                    i += 1;
+                    output_path = args[i].clone();
+                }
+                "--threshold" => {
+                    i += 1;
+                    threshold = args[i].parse().expect("--threshold must be 0-255.");
+                }
+                "--background" => {
+                    i += 1;
+                    background = BackgroundSide::parse(&args[i]);
+                }
+                "--connectivity" => {
+                    i += 1;
+                    connectivity = ConnectivityArg::parse(&args[i]);
+                }
+                flag => panic!("Unknown flag: {}", flag),
+            }
+            i += 1;
+        }
+
+        ImageModeArgs {
+            image_path: image_path.expect("--image <path> is required."),
+            output_path,
+            threshold,
+            background,
+            connectivity,
+        }
+    }
+}
+
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.is_empty() {
+        run_stdin_demo();
+    } else {
+        run_image_mode(ImageModeArgs::parse(&args));
+    }
+}
+
+/// Loads a raster file, thresholds it to a binary mask, labels it, and
+/// writes out a colorized label map as a PNG.
+fn run_image_mode(args: ImageModeArgs) {
+    let img = image::open(&args.image_path)
+        .unwrap_or_else(|err| panic!("Failed to open {}: {}", args.image_path, err))
+        .to_luma8();
+
+    let (w, h) = img.dimensions();
+
+    // Threshold the image into a binary mask.
+    let binary_image: Vec<Vec<bool>> = (0..h)
+        .map(|y| {
+            (0..w)
+                .map(|x| {
+                    args.background
+                        .is_foreground(img.get_pixel(x, y).0[0], args.threshold)
+                })
+                .collect()
+        })
+        .collect();
+
+    let labeled_image = args.connectivity.label(&binary_image);
+
+    // Assign each label a distinct color and render the colorized label map.
+    let palette = label_palette(&labeled_image);
+    let output = RgbImage::from_fn(w, h, |x, y| {
+        palette[(labeled_image[y as usize][x as usize] + 1) as usize]
+    });
+
+    output
+        .save(&args.output_path)
+        .unwrap_or_else(|err| panic!("Failed to write {}: {}", args.output_path, err));
+
+    println!("Wrote labeled image to {}", args.output_path);
+}
+
+/// Builds a color palette indexed by `label + 1`, so that index `0` is the
+/// background color (black) and every distinct label gets a visually
+/// distinct color, generated by rotating the hue by the golden angle.
+fn label_palette(labeled_image: &Vec<Vec<labeling::LabelType>>) -> Vec<Rgb<u8>> {
+    let label_cnt = labeled_image
+        .iter()
+        .flatten()
+        .copied()
+        .max()
+        .map_or(0, |max_label| max_label + 1);
+
+    let mut palette = vec![Rgb([0, 0, 0])];
+    for label in 0..label_cnt {
+        // Golden angle hue rotation spreads labels evenly around the color wheel.
+        let hue = (label as f64 * 137.508) % 360.0;
+        palette.push(hue_to_rgb(hue));
+    }
+
+    palette
+}
+
+/// Converts a hue (in degrees, full saturation and value) to an RGB color.
+fn hue_to_rgb(hue: f64) -> Rgb<u8> {
+    let c = 255.0;
+    let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match hue as u32 {
+        0..=59 => (c, x, 0.0),
+        60..=119 => (x, c, 0.0),
+        120..=179 => (0.0, c, x),
+        180..=239 => (0.0, x, c),
+        240..=299 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    Rgb([r as u8, g as u8, b as u8])
+}
+
+/// Runs the original stdin-driven demo: reads a `0`/`1` grid from standard
+/// input and prints both four- and eight-neighborhood labelings.
+fn run_stdin_demo() {
     // Reads binary images from standard input.
     let mut binary_image: Vec<Vec<bool>> = vec![];
     for line in io::stdin().lines() {
@@ -77,3 +285,86 @@ fn main() {
         println!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hue_to_rgb_maps_primary_hues() {
+        assert_eq!(hue_to_rgb(0.0), Rgb([255, 0, 0]));
+        assert_eq!(hue_to_rgb(120.0), Rgb([0, 255, 0]));
+        assert_eq!(hue_to_rgb(240.0), Rgb([0, 0, 255]));
+    }
+
+    #[test]
+    fn label_palette_reserves_index_zero_for_background() {
+        let labeled_image = vec![vec![-1, 0, 1], vec![1, -1, 2]];
+
+        let palette = label_palette(&labeled_image);
+
+        // Background (index 0) plus labels 0, 1 and 2.
+        assert_eq!(palette.len(), 4);
+        assert_eq!(palette[0], Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn label_palette_of_all_background_image_is_just_the_background_color() {
+        let labeled_image = vec![vec![-1, -1], vec![-1, -1]];
+
+        let palette = label_palette(&labeled_image);
+
+        assert_eq!(palette, vec![Rgb([0, 0, 0])]);
+    }
+
+    #[test]
+    fn background_side_black_treats_bright_pixels_as_foreground() {
+        assert!(BackgroundSide::Black.is_foreground(200, 128));
+        assert!(!BackgroundSide::Black.is_foreground(50, 128));
+    }
+
+    #[test]
+    fn background_side_white_treats_dark_pixels_as_foreground() {
+        assert!(BackgroundSide::White.is_foreground(50, 128));
+        assert!(!BackgroundSide::White.is_foreground(200, 128));
+    }
+
+    #[test]
+    fn image_mode_args_parse_applies_defaults() {
+        let args = ImageModeArgs::parse(&["--image".to_string(), "in.png".to_string()]);
+
+        assert_eq!(args.image_path, "in.png");
+        assert_eq!(args.output_path, DEFAULT_OUTPUT_PATH);
+        assert_eq!(args.threshold, DEFAULT_THRESHOLD);
+        assert!(matches!(args.background, BackgroundSide::Black));
+        assert!(matches!(args.connectivity, ConnectivityArg::Eight));
+    }
+
+    #[test]
+    fn image_mode_args_parse_reads_every_flag() {
+        let args = ImageModeArgs::parse(&[
+            "--image".to_string(),
+            "in.png".to_string(),
+            "--output".to_string(),
+            "out.png".to_string(),
+            "--threshold".to_string(),
+            "100".to_string(),
+            "--background".to_string(),
+            "white".to_string(),
+            "--connectivity".to_string(),
+            "four".to_string(),
+        ]);
+
+        assert_eq!(args.image_path, "in.png");
+        assert_eq!(args.output_path, "out.png");
+        assert_eq!(args.threshold, 100);
+        assert!(matches!(args.background, BackgroundSide::White));
+        assert!(matches!(args.connectivity, ConnectivityArg::Four));
+    }
+
+    #[test]
+    #[should_panic(expected = "--image <path> is required.")]
+    fn image_mode_args_parse_requires_image_flag() {
+        let _ = ImageModeArgs::parse(&[]);
+    }
+}
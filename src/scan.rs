@@ -0,0 +1,89 @@
+use crate::grid::Image;
+use crate::union_find::UnionFind;
+use crate::{Connectivity, LabelType};
+
+/// Offsets (relative to the current pixel) of the already-visited neighbors
+/// to examine for the given connectivity.
+pub(crate) fn neighbor_offsets(conn: Connectivity) -> &'static [(i64, i64)] {
+    match conn {
+        Connectivity::Four => &[(-1, 0), (0, -1)],
+        Connectivity::Eight => &[(-1, -1), (-1, 0), (-1, 1), (0, -1)],
+    }
+}
+
+/// It runs the first (provisional-labeling) pass of the union-find two-pass
+/// labeling algorithm, shared by [`crate::labeling_with_stats`] and
+/// [`crate::component_bitmaps`]. [`crate::four_neighborhood_based_labeling`],
+/// [`crate::eight_neighborhood_based_labeling`] and
+/// [`crate::connected_components`] run their own scans, and
+/// [`crate::Labeler`] labels row-at-a-time.
+///
+/// # Arguments
+///
+/// * `binary_image` - Binary image represented by a two-dimensional boolean vector
+/// * `conn` - Neighborhood used to decide whether two pixels are connected
+///
+/// # Return
+///
+/// A tuple of the tentatively labeled image and the union-find expressing
+/// the connections between its provisional labels.
+///
+/// # Panics
+///
+/// The function panics if `binary_image` is not a rectangle.
+pub(crate) fn scan(binary_image: &Vec<Vec<bool>>, conn: Connectivity) -> (Vec<Vec<LabelType>>, UnionFind) {
+    // Flatten the input into a single contiguous buffer. This also checks
+    // that `binary_image` is a non-empty rectangle, up front instead of via
+    // a per-row `assert_eq!` inside the hot loop below.
+    let binary_image = Image::from(binary_image);
+
+    let w = binary_image.width();
+    let h = binary_image.height();
+
+    let mut labeled_image = Image::new(w, h, -1);
+
+    let mut uf = UnionFind::new(0);
+
+    for i in 0..h {
+        for j in 0..w {
+            // If the current pixel is background, skip the process.
+            if !binary_image[(j, i)] {
+                continue;
+            }
+
+            // Labels of the already-visited neighbors, according to the chosen connectivity.
+            // Collected eagerly into a `Vec` (rather than left as a lazy
+            // iterator) because the closure below borrows `labeled_image`,
+            // and that borrow must end before `labeled_image[(j, i)]` is
+            // written a few lines down.
+            let neighbor_labels: Vec<LabelType> = neighbor_offsets(conn)
+                .iter()
+                .filter_map(|&(di, dj)| {
+                    let ni = i as i64 + di;
+                    let nj = j as i64 + dj;
+                    if ni < 0 || nj < 0 || ni >= h as i64 || nj >= w as i64 {
+                        return None;
+                    }
+                    let (ni, nj) = (ni as usize, nj as usize);
+                    if binary_image[(nj, ni)] {
+                        Some(labeled_image[(nj, ni)])
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            if let Some((&first_label, rest)) = neighbor_labels.split_first() {
+                labeled_image[(j, i)] = first_label;
+                rest.iter().for_each(|&other_label| {
+                    uf.merge(first_label as usize, other_label as usize);
+                });
+            } else {
+                labeled_image[(j, i)] = uf.get_elem_num() as LabelType;
+                uf.add();
+            }
+        }
+    }
+
+    (Vec::from(&labeled_image), uf)
+}
@@ -0,0 +1,235 @@
+use crate::scan::scan;
+use crate::union_find::UnionFind;
+use crate::{Connectivity, LabelType};
+
+/// Statistics computed for a single connected component.
+#[derive(Debug, Clone)]
+pub struct RegionStats {
+    /// Number of foreground pixels in the component.
+    pub area: usize,
+    /// Smallest row index occupied by the component.
+    pub min_row: usize,
+    /// Largest row index occupied by the component.
+    pub max_row: usize,
+    /// Smallest column index occupied by the component.
+    pub min_col: usize,
+    /// Largest column index occupied by the component.
+    pub max_col: usize,
+    /// Mean `(row, col)` position of the component's pixels.
+    pub centroid: (f64, f64),
+    /// Coordinates `(row, col)` of every foreground pixel in the component.
+    ///
+    /// Only populated when `collect_pixels` is passed to [`labeling_with_stats`].
+    pub pixels: Option<Vec<(usize, usize)>>,
+}
+
+/// Running accumulator for the [`RegionStats`] of a reassigned label.
+struct StatsBuilder {
+    area: usize,
+    min_row: usize,
+    max_row: usize,
+    min_col: usize,
+    max_col: usize,
+    row_sum: u64,
+    col_sum: u64,
+    pixels: Option<Vec<(usize, usize)>>,
+}
+
+impl StatsBuilder {
+    fn new(row: usize, col: usize, collect_pixels: bool) -> Self {
+        let mut builder = StatsBuilder {
+            area: 0,
+            min_row: row,
+            max_row: row,
+            min_col: col,
+            max_col: col,
+            row_sum: 0,
+            col_sum: 0,
+            pixels: collect_pixels.then(Vec::new),
+        };
+        builder.push(row, col);
+        builder
+    }
+
+    fn push(&mut self, row: usize, col: usize) {
+        self.area += 1;
+        self.min_row = self.min_row.min(row);
+        self.max_row = self.max_row.max(row);
+        self.min_col = self.min_col.min(col);
+        self.max_col = self.max_col.max(col);
+        self.row_sum += row as u64;
+        self.col_sum += col as u64;
+        if let Some(pixels) = &mut self.pixels {
+            pixels.push((row, col));
+        }
+    }
+
+    fn build(self) -> RegionStats {
+        RegionStats {
+            area: self.area,
+            min_row: self.min_row,
+            max_row: self.max_row,
+            min_col: self.min_col,
+            max_col: self.max_col,
+            centroid: (
+                self.row_sum as f64 / self.area as f64,
+                self.col_sum as f64 / self.area as f64,
+            ),
+            pixels: self.pixels,
+        }
+    }
+}
+
+/// It executes connected-component labeling and computes per-component
+/// statistics (area, bounding box, centroid and, optionally, the pixel
+/// coordinates) in the same passes.
+///
+/// # Arguments
+///
+/// * `binary_image` - Binary image represented by a two-dimensional boolean vector
+/// * `conn` - Neighborhood used to decide whether two pixels are connected
+/// * `collect_pixels` - Whether to record every foreground pixel's coordinates in the returned stats
+///
+/// # Return
+///
+/// A tuple of the labeled image and the `RegionStats` for each label,
+/// indexed by the reassigned label.
+///
+/// # Panics
+///
+/// The function panics if `binary_image` is not a rectangle.
+pub fn labeling_with_stats(
+    binary_image: &Vec<Vec<bool>>,
+    conn: Connectivity,
+    collect_pixels: bool,
+) -> (Vec<Vec<LabelType>>, Vec<RegionStats>) {
+    // Run the provisional-labeling pass shared with `component_bitmaps`.
+    let (mut labeled_image, mut uf) = scan(binary_image, conn);
+
+    // Reassign labels and fold the per-pixel bounding box/centroid sums into
+    // the reassigned-label indices in the same pass.
+    let region_stats = reassign_labels_with_stats(&mut labeled_image, &mut uf, collect_pixels);
+
+    (labeled_image, region_stats)
+}
+
+/// It reassigns labels, building the [`RegionStats`] of each reassigned label
+/// along the way.
+///
+/// # Arguments
+///
+/// * `labeled_image` - Tentatively labeled image
+/// * `uf` - Union-Find expressing the connection between the labels
+/// * `collect_pixels` - Whether to record every foreground pixel's coordinates in the returned stats
+///
+/// # Panics
+///
+/// This function panics if the height or width of the image is zero.
+fn reassign_labels_with_stats(
+    labeled_image: &mut Vec<Vec<LabelType>>,
+    uf: &mut UnionFind,
+    collect_pixels: bool,
+) -> Vec<RegionStats> {
+    // Height of image
+    let h = labeled_image.len();
+
+    // Height must be greater than or equal to 1
+    assert!(h >= 1);
+
+    // Width of image
+    let w = labeled_image[0].len();
+
+    // Width must be greater than or equal to 1
+    assert!(w >= 1);
+
+    // Vec for reassigned labels
+    // The i-th index stores the reassigned label for provisional label i.
+    let mut reassignment_labels: Vec<Option<LabelType>> = vec![None; uf.get_elem_num()];
+
+    // Stats builder for each reassigned label, indexed the same way as
+    // `reassignment_labels`. `StatsBuilder` does not implement `Clone`,
+    // which rules out `vec![None; n]`.
+    let mut stats_builders: Vec<Option<StatsBuilder>> =
+        (0..uf.get_elem_num()).map(|_| None).collect();
+
+    // Counting the number of labels
+    let mut label_cnt = 0;
+
+    for i in 0..h {
+        for j in 0..w {
+            // If the current pixel is background, skip the process.
+            if labeled_image[i][j] == -1 {
+                continue;
+            }
+
+            // Representative value of the connected component containing the label of the current pixel
+            let leader_label = uf.leader(labeled_image[i][j] as usize);
+            // Reassigned label
+            let reassigned_label = &mut reassignment_labels[leader_label];
+
+            // If no label has been set for reassignment, create a new label.
+            if reassigned_label.is_none() {
+                *reassigned_label = Some(label_cnt);
+                label_cnt += 1;
+            }
+
+            let reassigned_label = reassigned_label.unwrap();
+
+            // Reassign labels.
+            labeled_image[i][j] = reassigned_label;
+
+            // Fold the current pixel into the reassigned label's stats.
+            match &mut stats_builders[leader_label] {
+                Some(builder) => builder.push(i, j),
+                builder @ None => *builder = Some(StatsBuilder::new(i, j, collect_pixels)),
+            }
+        }
+    }
+
+    // Collect the builders into `RegionStats`, ordered by reassigned label.
+    let mut region_stats: Vec<Option<RegionStats>> = vec![None; label_cnt as usize];
+    for (leader_label, reassigned_label) in reassignment_labels.into_iter().enumerate() {
+        if let Some(reassigned_label) = reassigned_label {
+            let builder = stats_builders[leader_label].take().unwrap();
+            region_stats[reassigned_label as usize] = Some(builder.build());
+        }
+    }
+
+    region_stats.into_iter().map(Option::unwrap).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_area_bbox_and_centroid_per_component() {
+        // . # #
+        // . . .
+        // # . .
+        let binary_image = vec![
+            vec![false, true, true],
+            vec![false, false, false],
+            vec![true, false, false],
+        ];
+
+        let (labeled_image, region_stats) =
+            labeling_with_stats(&binary_image, Connectivity::Four, true);
+
+        // The two `#` pixels on row 0 are 4-connected; the one on row 2 is not.
+        assert_eq!(labeled_image[0][1], labeled_image[0][2]);
+        assert_ne!(labeled_image[0][1], labeled_image[2][0]);
+        assert_eq!(region_stats.len(), 2);
+
+        let top_run = &region_stats[labeled_image[0][1] as usize];
+        assert_eq!(top_run.area, 2);
+        assert_eq!((top_run.min_row, top_run.max_row), (0, 0));
+        assert_eq!((top_run.min_col, top_run.max_col), (1, 2));
+        assert_eq!(top_run.centroid, (0.0, 1.5));
+        assert_eq!(top_run.pixels.as_ref().unwrap().len(), 2);
+
+        let lone_pixel = &region_stats[labeled_image[2][0] as usize];
+        assert_eq!(lone_pixel.area, 1);
+        assert_eq!(lone_pixel.centroid, (2.0, 0.0));
+    }
+}
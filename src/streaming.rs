@@ -0,0 +1,214 @@
+use crate::scan::neighbor_offsets;
+use crate::union_find::UnionFind;
+use crate::{Connectivity, LabelType};
+
+/// Incremental, row-at-a-time connected-component labeler.
+///
+/// Unlike [`crate::four_neighborhood_based_labeling`] and
+/// [`crate::eight_neighborhood_based_labeling`], which require the whole
+/// image to be in memory up front, `Labeler` accepts one row at a time via
+/// [`Labeler::push_row`], which keeps only the previous row's provisional
+/// labels (`O(width)`, regardless of image height) plus the shared
+/// union-find live; the union-find gains one element per new provisional
+/// label, so its size is not bounded by the width. `push_row` returns that row's *provisional* labels
+/// immediately; labels in different rows that later turn out to belong to
+/// the same component are not yet merged into one number at that point.
+///
+/// Once every row has been pushed, [`Labeler::finish`] resolves the
+/// union-find leaders into a [`Remapper`]. Feeding the previously returned
+/// provisional rows through [`Remapper::remap_row`], in the same order they
+/// were pushed, produces the final, densely-numbered labels. This keeps the
+/// two-pass union-find algorithm's amortized `O(α(n))` merges while leaving
+/// it up to the caller whether to buffer provisional rows (e.g. to disk, or
+/// not at all if only live component membership is needed).
+pub struct Labeler {
+    conn: Connectivity,
+    width: Option<usize>,
+    uf: UnionFind,
+    previous_row: Option<Vec<LabelType>>,
+}
+
+impl Labeler {
+    /// Creates a labeler that merges foreground pixels according to `conn`.
+    pub fn new(conn: Connectivity) -> Self {
+        Labeler {
+            conn,
+            width: None,
+            uf: UnionFind::new(0),
+            previous_row: None,
+        }
+    }
+
+    /// Feeds the next row of the binary image to the labeler and returns its
+    /// provisional labels.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `row` is empty, or if its length differs from
+    /// a previously pushed row's length.
+    pub fn push_row(&mut self, row: &[bool]) -> Vec<LabelType> {
+        let width = *self.width.get_or_insert(row.len());
+        assert!(width >= 1, "Width must be greater than or equal to 1");
+        assert_eq!(row.len(), width, "Image shape must be rectangular.");
+
+        let mut labeled_row = vec![-1; width];
+
+        for j in 0..width {
+            // If the current pixel is background, skip the process.
+            if !row[j] {
+                continue;
+            }
+
+            // Labels of the already-visited neighbors, according to the chosen connectivity.
+            // Collected eagerly into a `Vec` (rather than left as a lazy
+            // iterator) because the closure below borrows `labeled_row`,
+            // and that borrow must end before `labeled_row[j]` is written a
+            // few lines down.
+            let neighbor_labels: Vec<LabelType> = neighbor_offsets(self.conn)
+                .iter()
+                .filter_map(|&(di, dj)| {
+                    let nj = j as i64 + dj;
+                    if nj < 0 || nj >= width as i64 {
+                        return None;
+                    }
+                    let nj = nj as usize;
+
+                    let label = if di == 0 {
+                        // Same row, already-labeled to the left.
+                        labeled_row[nj]
+                    } else {
+                        // Previous row, if there is one.
+                        self.previous_row.as_ref().map_or(-1, |prev| prev[nj])
+                    };
+
+                    (label != -1).then_some(label)
+                })
+                .collect();
+
+            if let Some((&first_label, rest)) = neighbor_labels.split_first() {
+                labeled_row[j] = first_label;
+                rest.iter().for_each(|&other_label| {
+                    self.uf.merge(first_label as usize, other_label as usize);
+                });
+            } else {
+                labeled_row[j] = self.uf.get_elem_num() as LabelType;
+                self.uf.add();
+            }
+        }
+
+        self.previous_row = Some(labeled_row.clone());
+
+        labeled_row
+    }
+
+    /// Consumes the labeler and returns a [`Remapper`] that resolves its
+    /// union-find leaders into final, densely-numbered labels.
+    pub fn finish(self) -> Remapper {
+        Remapper {
+            reassignment_labels: vec![None; self.uf.get_elem_num()],
+            label_cnt: 0,
+            uf: self.uf,
+        }
+    }
+}
+
+/// Second pass of the streaming labeler, produced by [`Labeler::finish`].
+///
+/// Translates the provisional rows returned by [`Labeler::push_row`] into
+/// final labels by resolving union-find leaders. Rows must be fed back via
+/// [`Remapper::remap_row`] in the same order they were originally pushed,
+/// so that reassigned labels are handed out in the same row-major order
+/// [`crate::four_neighborhood_based_labeling`]'s reassignment pass uses.
+pub struct Remapper {
+    uf: UnionFind,
+    reassignment_labels: Vec<Option<LabelType>>,
+    label_cnt: LabelType,
+}
+
+impl Remapper {
+    /// Resolves one provisional row, as returned by [`Labeler::push_row`],
+    /// into final labels.
+    pub fn remap_row(&mut self, provisional_row: &[LabelType]) -> Vec<LabelType> {
+        provisional_row
+            .iter()
+            .map(|&label| {
+                // If the current pixel is background, leave it as-is.
+                if label == -1 {
+                    return -1;
+                }
+
+                // Representative value of the connected component containing this label
+                let leader_label = self.uf.leader(label as usize);
+                // Reassigned label
+                let reassigned_label = &mut self.reassignment_labels[leader_label];
+
+                // If no label has been set for reassignment, create a new label.
+                if reassigned_label.is_none() {
+                    *reassigned_label = Some(self.label_cnt);
+                    self.label_cnt += 1;
+                }
+
+                reassigned_label.unwrap()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_whole_image_labeling_when_fed_row_at_a_time() {
+        // # # .
+        // . . .
+        // . # #
+        let rows = [
+            vec![true, true, false],
+            vec![false, false, false],
+            vec![false, true, true],
+        ];
+
+        let mut labeler = Labeler::new(Connectivity::Eight);
+        let provisional_rows: Vec<Vec<LabelType>> =
+            rows.iter().map(|row| labeler.push_row(row)).collect();
+
+        let mut remapper = labeler.finish();
+        let labeled: Vec<Vec<LabelType>> = provisional_rows
+            .iter()
+            .map(|row| remapper.remap_row(row))
+            .collect();
+
+        let binary_image: Vec<Vec<bool>> = rows.to_vec();
+        let expected = crate::eight_neighborhood_based_labeling(&binary_image);
+
+        assert_eq!(labeled, expected);
+    }
+
+    #[test]
+    fn push_row_keeps_only_the_previous_row_live() {
+        let mut labeler = Labeler::new(Connectivity::Four);
+
+        // Pushing many rows must not grow any state beyond the previous row
+        // and the union-find: `Labeler` holds no `Vec` of full-image size.
+        for _ in 0..1000 {
+            labeler.push_row(&[true, false, true]);
+        }
+
+        assert_eq!(labeler.previous_row.as_ref().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn remap_row_assigns_dense_labels_in_row_major_order() {
+        let mut labeler = Labeler::new(Connectivity::Four);
+        let row0 = labeler.push_row(&[true, false]);
+        let row1 = labeler.push_row(&[false, true]);
+
+        let mut remapper = labeler.finish();
+        let remapped0 = remapper.remap_row(&row0);
+        let remapped1 = remapper.remap_row(&row1);
+
+        assert_eq!(remapped0, vec![0, -1]);
+        assert_eq!(remapped1, vec![-1, 1]);
+    }
+}